@@ -1,4 +1,138 @@
-use std::{str::Chars, vec};
+use std::{fmt, io::Read};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub col: usize,
+    pub offset: usize,
+}
+
+impl Position {
+    fn start() -> Self {
+        Position {
+            line: 1,
+            col: 1,
+            offset: 0,
+        }
+    }
+
+    fn advance(&mut self, char: char) {
+        if char == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+        self.offset += 1;
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.col)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub start: Position,
+    pub len: usize,
+}
+
+impl Span {
+    fn point(start: Position) -> Self {
+        Span { start, len: 1 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    UnexpectedChar { char: char, span: Span },
+    UnterminatedString { span: Span },
+    MalformedEscape { reason: String, span: Span },
+    MalformedNumber { reason: String, span: Span },
+    UnexpectedToken {
+        expected: String,
+        found: String,
+        span: Span,
+    },
+    UnexpectedEof { span: Span },
+    Io(String),
+}
+
+impl ParseError {
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ParseError::UnexpectedChar { span, .. }
+            | ParseError::UnterminatedString { span }
+            | ParseError::MalformedEscape { span, .. }
+            | ParseError::MalformedNumber { span, .. }
+            | ParseError::UnexpectedToken { span, .. }
+            | ParseError::UnexpectedEof { span } => Some(*span),
+            ParseError::Io(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedChar { char, span } => {
+                write!(f, "unexpected character '{char}' at {}", span.start)
+            }
+            ParseError::UnterminatedString { span } => {
+                write!(f, "unterminated string at {}", span.start)
+            }
+            ParseError::MalformedEscape { reason, span } => {
+                write!(f, "{reason} at {}", span.start)
+            }
+            ParseError::MalformedNumber { reason, span } => {
+                write!(f, "{reason} at {}", span.start)
+            }
+            ParseError::UnexpectedToken {
+                expected,
+                found,
+                span,
+            } => write!(f, "expected {expected} but found {found} at {}", span.start),
+            ParseError::UnexpectedEof { span } => {
+                write!(f, "unexpected end of input at {}", span.start)
+            }
+            ParseError::Io(reason) => write!(f, "I/O error: {reason}"),
+        }
+    }
+}
+
+/// Renders a caret-style diagnostic pointing at `error`'s span within `source`,
+/// ariadne-style: the message, the `line:col` location, then the offending
+/// line with a `^` underline beneath the span.
+pub fn render_diagnostic(source: &str, error: &ParseError) -> String {
+    let Some(span) = error.span() else {
+        return format!("error: {error}");
+    };
+
+    let line_text = source.lines().nth(span.start.line - 1).unwrap_or("");
+    let gutter = format!("{}", span.start.line);
+    let padding = " ".repeat(gutter.len());
+
+    format!(
+        "error: {error}\n{padding} --> {}:{}\n{padding} |\n{gutter} | {line_text}\n{padding} | {}{}",
+        span.start.line,
+        span.start.col,
+        " ".repeat(span.start.col.saturating_sub(1)),
+        "^".repeat(span.len.max(1)),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum TokenType {
@@ -15,421 +149,696 @@ enum TokenType {
     EndOfFile,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Token {
     token_type: TokenType,
     value: Option<String>,
-    position: usize,
-}
-
-pub struct Lexer {
-    content: String,
-    position: usize,
-    tokens: Vec<Token>,
+    position: Position,
+    /// Set for `TokenType::Number` tokens to the fraction/exponent bit the
+    /// number scanner already computed, so callers don't have to re-derive
+    /// it by string-scanning for `.`/`e`. Meaningless for other token types.
+    is_float: bool,
 }
 
 impl Token {
-    fn new(token_type: TokenType, value: Option<String>, position: usize) -> Self {
+    fn new(token_type: TokenType, value: Option<String>, position: Position) -> Self {
         Token {
             token_type,
             value,
             position,
+            is_float: false,
         }
     }
-}
 
-impl Lexer {
-    pub fn new(content: String) -> Self {
-        Lexer {
-            content,
-            position: 0,
-            tokens: Vec::new(),
+    fn new_number(content: String, is_float: bool, position: Position) -> Self {
+        Token {
+            token_type: TokenType::Number,
+            value: Some(content),
+            position,
+            is_float,
         }
     }
 
-    pub fn get_tokens(&self) -> &Vec<Token> {
-        return &self.tokens;
+    fn span(&self) -> Span {
+        Span::point(self.position)
     }
 
-    fn tokenize_string(chars: &mut Chars) -> (String, usize) {
-        let mut count = 0;
-        let mut str_content = String::new();
-
-        while let Some(char) = chars.next() {
-            count += 1;
-
-            if char == '\n' {
-                break;
-            } else if char != '"' {
-                str_content.push_str(&char.to_string());
-            } else {
-                break;
-            }
+    /// A human-readable description of this token for "found X" diagnostics.
+    fn describe(&self) -> String {
+        match &self.value {
+            Some(value) => match self.token_type {
+                TokenType::String => format!("\"{value}\""),
+                _ => value.clone(),
+            },
+            None => match self.token_type {
+                TokenType::LeftBrace => "'{'".to_string(),
+                TokenType::RightBrace => "'}'".to_string(),
+                TokenType::LeftBracket => "'['".to_string(),
+                TokenType::RightBracket => "']'".to_string(),
+                TokenType::Colon => "':'".to_string(),
+                TokenType::Comma => "','".to_string(),
+                TokenType::EndOfFile => "end of input".to_string(),
+                _ => "token".to_string(),
+            },
         }
-
-        return (str_content, count);
     }
+}
 
-    fn tokenize_non_string(chars: &mut Chars, content: &mut String) -> (usize, Option<char>) {
-        let mut count = 0;
-        let mut end_char = None;
+/// Tokenizes `content` on demand: each `next_token`/`peek` call scans only as much
+/// source as is needed for one token, so callers never pay for a fully
+/// materialized `Vec<Token>`.
+pub struct Lexer {
+    content: String,
+    offset: usize,
+    position: Position,
+    pending: Option<Token>,
+    peeked: Option<Token>,
+    done: bool,
+}
 
-        while let Some(char) = chars.next() {
-            count += 1;
+impl Lexer {
+    pub fn new(content: String) -> Self {
+        Lexer {
+            content,
+            offset: 0,
+            position: Position::start(),
+            pending: None,
+            peeked: None,
+            done: false,
+        }
+    }
 
-            if char.is_whitespace() {
-                break;
-            } else if char != ',' && char != '}' && char != ']' {
-                content.push_str(&char.to_string());
-            } else {
-                end_char = Some(char);
-            }
+    /// Consumes and returns the next token.
+    pub fn next_token(&mut self) -> Result<Token, ParseError> {
+        if let Some(token) = self.peeked.take() {
+            return Ok(token);
         }
+        self.scan_next()
+    }
 
-        return (count, end_char);
+    /// Returns the next token without consuming it.
+    pub fn peek(&mut self) -> Result<&Token, ParseError> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.scan_next()?);
+        }
+        Ok(self.peeked.as_ref().unwrap())
     }
 
-    fn non_string_token_gen(
-        chars: &mut Chars,
-        char: char,
-        token_type: TokenType,
-        position: usize,
-    ) -> (Vec<Token>, usize) {
-        let mut tokens = Vec::new();
+    fn next_char(&mut self) -> Option<char> {
+        let char = self.content[self.offset..].chars().next()?;
+        self.offset += char.len_utf8();
+        Some(char)
+    }
 
-        let mut content = char.to_string();
-        let (count, end_char) = Lexer::tokenize_non_string(chars, &mut content);
+    fn peek_char(&self) -> Option<char> {
+        self.content[self.offset..].chars().next()
+    }
 
-        match end_char {
-            Some(char) => {
-                tokens.push(Token::new(token_type, Some(content), position + count));
-                if char == ',' {
-                    tokens.push(Token::new(TokenType::Comma, None, position + count));
-                } else if char == '}' {
-                    tokens.push(Token::new(TokenType::LeftBrace, None, position + count));
-                } else {
-                    tokens.push(Token::new(TokenType::LeftBracket, None, position + count));
-                }
-            }
-            None => tokens.push(Token::new(token_type, Some(content), position + count)),
+    fn scan_next(&mut self) -> Result<Token, ParseError> {
+        if let Some(token) = self.pending.take() {
+            return Ok(token);
+        }
+        if self.done {
+            return Ok(Token::new(TokenType::EndOfFile, None, self.position));
         }
 
-        return (tokens, count);
-    }
+        loop {
+            let char = match self.next_char() {
+                Some(char) => char,
+                None => {
+                    self.done = true;
+                    return Ok(Token::new(TokenType::EndOfFile, None, self.position));
+                }
+            };
 
-    pub fn tokenize(&mut self) {
-        let mut chars = self.content.chars();
+            let start = self.position;
+            self.position.advance(char);
 
-        while let Some(char) = chars.next() {
             if char.is_whitespace() {
                 continue;
             } else if char == '{' {
-                self.tokens
-                    .push(Token::new(TokenType::LeftBrace, None, self.position));
+                return Ok(Token::new(TokenType::LeftBrace, None, start));
             } else if char == '}' {
-                self.tokens
-                    .push(Token::new(TokenType::RightBrace, None, self.position));
+                return Ok(Token::new(TokenType::RightBrace, None, start));
             } else if char == '[' {
-                self.tokens
-                    .push(Token::new(TokenType::LeftBracket, None, self.position));
+                return Ok(Token::new(TokenType::LeftBracket, None, start));
             } else if char == ']' {
-                self.tokens
-                    .push(Token::new(TokenType::RightBracket, None, self.position));
+                return Ok(Token::new(TokenType::RightBracket, None, start));
             } else if char == ':' {
-                self.tokens
-                    .push(Token::new(TokenType::Colon, None, self.position));
+                return Ok(Token::new(TokenType::Colon, None, start));
             } else if char == ',' {
-                self.tokens
-                    .push(Token::new(TokenType::Comma, None, self.position));
+                return Ok(Token::new(TokenType::Comma, None, start));
             } else if char == '"' {
-                let (str_content, count) = Lexer::tokenize_string(&mut chars);
-                self.position += count;
-                self.tokens.push(Token::new(
-                    TokenType::String,
-                    Some(str_content),
-                    self.position,
-                ));
+                let str_content = self.tokenize_string()?;
+                return Ok(Token::new(TokenType::String, Some(str_content), start));
             } else if char == 'f' || char == 't' {
-                let (mut tokens, count) = Lexer::non_string_token_gen(
-                    &mut chars,
-                    char,
-                    TokenType::Boolean,
-                    self.position,
-                );
-                self.tokens.append(&mut tokens);
-                self.position += count;
+                return Ok(self.non_string_token(char, TokenType::Boolean, start));
             } else if char == 'n' {
-                let (mut tokens, count) =
-                    Lexer::non_string_token_gen(&mut chars, char, TokenType::Null, self.position);
-                self.tokens.append(&mut tokens);
-                self.position += count;
+                return Ok(self.non_string_token(char, TokenType::Null, start));
+            } else if char == '-' || char.is_ascii_digit() {
+                let (content, is_float) = self.tokenize_number(char)?;
+                return Ok(Token::new_number(content, is_float, start));
             } else {
-                let (mut tokens, count) =
-                    Lexer::non_string_token_gen(&mut chars, char, TokenType::Number, self.position);
-                self.tokens.append(&mut tokens);
-                self.position += count;
+                return Err(ParseError::UnexpectedChar {
+                    char,
+                    span: Span::point(start),
+                });
+            }
+        }
+    }
+
+    fn tokenize_string(&mut self) -> Result<String, ParseError> {
+        let mut str_content = String::new();
+
+        loop {
+            let char = self.next_char().ok_or(ParseError::UnterminatedString {
+                span: Span::point(self.position),
+            })?;
+            self.position.advance(char);
+
+            match char {
+                '"' => break,
+                '\\' => str_content.push(self.tokenize_escape()?),
+                _ => str_content.push(char),
             }
-            self.position += 1;
         }
 
-        self.tokens
-            .push(Token::new(TokenType::EndOfFile, None, self.position));
+        Ok(str_content)
     }
-}
 
-pub struct Parser<'a> {
-    tokens: &'a Vec<Token>,
-    current: usize,
-}
+    fn tokenize_escape(&mut self) -> Result<char, ParseError> {
+        let escape = self.next_char().ok_or(ParseError::UnterminatedString {
+            span: Span::point(self.position),
+        })?;
+        self.position.advance(escape);
+
+        let decoded = match escape {
+            '"' => '"',
+            '\\' => '\\',
+            '/' => '/',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let high = self.tokenize_unicode_escape()?;
+
+                if (0xD800..=0xDBFF).contains(&high) {
+                    let backslash = self.next_char().ok_or(ParseError::UnterminatedString {
+                        span: Span::point(self.position),
+                    })?;
+                    self.position.advance(backslash);
+                    if backslash != '\\' {
+                        return Err(ParseError::MalformedEscape {
+                            reason: "low surrogate must follow high surrogate".to_string(),
+                            span: Span::point(self.position),
+                        });
+                    }
+
+                    let marker = self.next_char().ok_or(ParseError::UnterminatedString {
+                        span: Span::point(self.position),
+                    })?;
+                    self.position.advance(marker);
+                    if marker != 'u' {
+                        return Err(ParseError::MalformedEscape {
+                            reason: "low surrogate must follow high surrogate".to_string(),
+                            span: Span::point(self.position),
+                        });
+                    }
 
-impl<'a> Parser<'a> {
-    pub fn new(tokens: &'a Vec<Token>) -> Self {
-        Parser { tokens, current: 0 }
+                    let low = self.tokenize_unicode_escape()?;
+                    if !(0xDC00..=0xDFFF).contains(&low) {
+                        return Err(ParseError::MalformedEscape {
+                            reason: "malformed surrogate pair".to_string(),
+                            span: Span::point(self.position),
+                        });
+                    }
+
+                    let combined = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+                    char::from_u32(combined).ok_or(ParseError::MalformedEscape {
+                        reason: "malformed surrogate pair".to_string(),
+                        span: Span::point(self.position),
+                    })?
+                } else if (0xDC00..=0xDFFF).contains(&high) {
+                    return Err(ParseError::MalformedEscape {
+                        reason: "unexpected low surrogate".to_string(),
+                        span: Span::point(self.position),
+                    });
+                } else {
+                    char::from_u32(high).ok_or(ParseError::MalformedEscape {
+                        reason: "malformed \\u escape".to_string(),
+                        span: Span::point(self.position),
+                    })?
+                }
+            }
+            other => {
+                return Err(ParseError::MalformedEscape {
+                    reason: format!("unknown escape sequence \\{other}"),
+                    span: Span::point(self.position),
+                })
+            }
+        };
+
+        Ok(decoded)
     }
 
-    fn token_error(position: usize, value: Option<&String>) -> String {
-        match value {
-            Some(value) => format!("Unexpected token {} at position {}.", value, position),
-            None => format!("Unexpected token at position {}.", position),
+    fn tokenize_unicode_escape(&mut self) -> Result<u32, ParseError> {
+        let mut hex = String::new();
+
+        for _ in 0..4 {
+            let digit = self.next_char().ok_or(ParseError::MalformedEscape {
+                reason: "unterminated \\u escape".to_string(),
+                span: Span::point(self.position),
+            })?;
+            self.position.advance(digit);
+            if !digit.is_ascii_hexdigit() {
+                return Err(ParseError::MalformedEscape {
+                    reason: format!("malformed \\u escape \\u{hex}{digit}"),
+                    span: Span::point(self.position),
+                });
+            }
+            hex.push(digit);
         }
+
+        u32::from_str_radix(&hex, 16).map_err(|_| ParseError::MalformedEscape {
+            reason: format!("malformed \\u escape \\u{hex}"),
+            span: Span::point(self.position),
+        })
     }
 
-    fn advance(&mut self) {
-        if self.current < self.tokens.len() - 1 {
-            self.current += 1;
+    fn tokenize_digits(&mut self, content: &mut String) -> usize {
+        let mut count = 0;
+
+        while let Some(next) = self.peek_char() {
+            if !next.is_ascii_digit() {
+                break;
+            }
+            let digit = self.next_char().unwrap();
+            self.position.advance(digit);
+            content.push(digit);
+            count += 1;
         }
+
+        count
     }
 
-    fn get_current_token(&self) -> &Token {
-        &self.tokens[self.current]
+    fn tokenize_number(&mut self, first: char) -> Result<(String, bool), ParseError> {
+        let mut content = String::new();
+        let mut is_float = false;
+        let mut digit = first;
+
+        if digit == '-' {
+            content.push(digit);
+            digit = self.next_char().ok_or(ParseError::MalformedNumber {
+                reason: "missing digits after '-'".to_string(),
+                span: Span::point(self.position),
+            })?;
+            self.position.advance(digit);
+        }
+
+        if !digit.is_ascii_digit() {
+            return Err(ParseError::MalformedNumber {
+                reason: format!("expected digit, found '{digit}'"),
+                span: Span::point(self.position),
+            });
+        }
+        content.push(digit);
+
+        if digit == '0' {
+            if matches!(self.peek_char(), Some(next) if next.is_ascii_digit()) {
+                return Err(ParseError::MalformedNumber {
+                    reason: "leading zero not allowed".to_string(),
+                    span: Span::point(self.position),
+                });
+            }
+        } else {
+            self.tokenize_digits(&mut content);
+        }
+
+        if let Some('.') = self.peek_char() {
+            is_float = true;
+            let dot = self.next_char().unwrap();
+            self.position.advance(dot);
+            content.push(dot);
+
+            if self.tokenize_digits(&mut content) == 0 {
+                return Err(ParseError::MalformedNumber {
+                    reason: "missing digits after decimal point".to_string(),
+                    span: Span::point(self.position),
+                });
+            }
+        }
+
+        if matches!(self.peek_char(), Some('e') | Some('E')) {
+            is_float = true;
+            let e = self.next_char().unwrap();
+            self.position.advance(e);
+            content.push(e);
+
+            if matches!(self.peek_char(), Some('+') | Some('-')) {
+                let sign = self.next_char().unwrap();
+                self.position.advance(sign);
+                content.push(sign);
+            }
+
+            if self.tokenize_digits(&mut content) == 0 {
+                return Err(ParseError::MalformedNumber {
+                    reason: "missing digits in exponent".to_string(),
+                    span: Span::point(self.position),
+                });
+            }
+        }
+
+        if let Some(next) = self.peek_char() {
+            if !(next.is_whitespace() || next == ',' || next == '}' || next == ']') {
+                return Err(ParseError::MalformedNumber {
+                    reason: format!("unexpected character '{next}'"),
+                    span: Span::point(self.position),
+                });
+            }
+        }
+
+        Ok((content, is_float))
     }
 
-    fn is_comma(&self) -> bool {
-        self.get_current_token().token_type == TokenType::Comma
+    fn tokenize_non_string(&mut self, content: &mut String) -> Option<(char, Position)> {
+        while let Some(char) = self.next_char() {
+            let delim_start = self.position;
+            self.position.advance(char);
+
+            if char.is_whitespace() {
+                return None;
+            } else if char == ',' || char == '}' || char == ']' {
+                return Some((char, delim_start));
+            }
+            content.push(char);
+        }
+
+        None
     }
 
-    fn is_colon(&self) -> bool {
-        self.get_current_token().token_type == TokenType::Colon
+    /// Scans a bare word literal (`true`/`false`/`null`). A trailing `,`/`}`/`]`
+    /// swallowed by the scan is stashed in `self.pending` and returned on the
+    /// following `next_token`/`peek` call instead of being dropped.
+    fn non_string_token(&mut self, char: char, token_type: TokenType, start: Position) -> Token {
+        let mut content = char.to_string();
+        let end_char = self.tokenize_non_string(&mut content);
+
+        match end_char {
+            Some((char, delim_start)) => {
+                let extra = if char == ',' {
+                    Token::new(TokenType::Comma, None, delim_start)
+                } else if char == '}' {
+                    Token::new(TokenType::RightBrace, None, delim_start)
+                } else {
+                    Token::new(TokenType::RightBracket, None, delim_start)
+                };
+                self.pending = Some(extra);
+                Token::new(token_type, Some(content), start)
+            }
+            None => Token::new(token_type, Some(content), start),
+        }
     }
+}
 
-    fn is_right_brace(&self) -> bool {
-        self.get_current_token().token_type == TokenType::RightBrace
+pub struct Parser {
+    lexer: Lexer,
+}
+
+impl Parser {
+    pub fn new(lexer: Lexer) -> Self {
+        Parser { lexer }
     }
 
-    fn is_right_bracket(&self) -> bool {
-        self.get_current_token().token_type == TokenType::RightBracket
+    fn token_error(expected: &str, token: &Token) -> ParseError {
+        if token.token_type == TokenType::EndOfFile {
+            ParseError::UnexpectedEof { span: token.span() }
+        } else {
+            ParseError::UnexpectedToken {
+                expected: expected.to_string(),
+                found: token.describe(),
+                span: token.span(),
+            }
+        }
     }
 
-    fn is_null(value: &String) -> bool {
-        return *value == "null".to_string();
+    fn peek(&mut self) -> Result<&Token, ParseError> {
+        self.lexer.peek()
     }
 
-    fn is_boolean(value: &String) -> bool {
-        return *value == "false".to_string() || *value == "true".to_string();
+    fn expect(&mut self, token_type: TokenType, expected: &str) -> Result<Token, ParseError> {
+        let token = self.lexer.next_token()?;
+        if token.token_type != token_type {
+            return Err(Parser::token_error(expected, &token));
+        }
+        Ok(token)
     }
 
-    fn is_number(value: &String) -> bool {
-        return value.parse::<f64>().is_ok();
+    fn is_null(value: &str) -> bool {
+        value == "null"
     }
 
-    fn is_not_valid_array_next_value(&self, token: &TokenType) -> bool {
-        vec![
-            TokenType::Colon,
-            TokenType::Comma,
-            TokenType::RightBrace,
-            TokenType::RightBracket,
-            TokenType::EndOfFile,
-        ]
-        .contains(token)
+    fn is_boolean(value: &str) -> bool {
+        value == "false" || value == "true"
     }
 
-    fn expect_end_of_file(&self) -> bool {
-        let token = self.tokens.get(self.current + 1);
+    fn is_number(value: &str) -> bool {
+        value.parse::<f64>().is_ok()
+    }
 
-        match token {
-            Some(token) => {
-                if token.token_type != TokenType::EndOfFile {
-                    return false;
-                }
+    fn number_value(literal: &str, is_float: bool) -> Value {
+        if !is_float {
+            if let Ok(int) = literal.parse::<i64>() {
+                return Value::Int(int);
             }
-            None => return false,
         }
 
-        true
+        Value::Float(literal.parse::<f64>().unwrap())
+    }
+
+    fn is_not_valid_array_next_value(&self, token_type: &TokenType) -> bool {
+        matches!(
+            token_type,
+            TokenType::Colon
+                | TokenType::Comma
+                | TokenType::RightBrace
+                | TokenType::RightBracket
+                | TokenType::EndOfFile
+        )
     }
 
-    fn get_value(value: Option<&String>) -> Result<&String, String> {
-        match value {
+    fn get_value(token: &Token) -> Result<&str, ParseError> {
+        match token.value.as_deref() {
             Some(value) => Ok(value),
-            None => Err("invalid token".to_string()),
+            None => Err(Parser::token_error("a token value", token)),
         }
     }
 
-    fn validate_value(&mut self) -> Result<(), String> {
-        let token = self.get_current_token();
+    fn parse_value(&mut self) -> Result<Value, ParseError> {
+        let token_type = self.peek()?.token_type;
 
-        match token.token_type {
-            TokenType::String => {}
+        let value = match token_type {
+            TokenType::String => {
+                let token = self.expect(TokenType::String, "a string")?;
+                Value::String(Parser::get_value(&token)?.to_string())
+            }
             TokenType::Boolean => {
-                let value = Parser::get_value(token.value.as_ref())?;
+                let token = self.expect(TokenType::Boolean, "true or false")?;
+                let value = Parser::get_value(&token)?;
                 if !Parser::is_boolean(value) {
-                    return Err(Parser::token_error(token.position, token.value.as_ref()));
+                    return Err(Parser::token_error("true or false", &token));
                 }
+                Value::Bool(value == "true")
             }
             TokenType::Number => {
-                let value = Parser::get_value(token.value.as_ref())?;
+                let token = self.expect(TokenType::Number, "a number")?;
+                let value = Parser::get_value(&token)?;
                 if !Parser::is_number(value) {
-                    return Err(Parser::token_error(token.position, token.value.as_ref()));
+                    return Err(Parser::token_error("a number", &token));
                 }
+                Parser::number_value(value, token.is_float)
             }
             TokenType::Null => {
-                let value = Parser::get_value(token.value.as_ref())?;
+                let token = self.expect(TokenType::Null, "null")?;
+                let value = Parser::get_value(&token)?;
                 if !Parser::is_null(value) {
-                    return Err(Parser::token_error(token.position, token.value.as_ref()));
+                    return Err(Parser::token_error("null", &token));
                 }
+                Value::Null
             }
             TokenType::LeftBrace => {
-                self.advance();
-                self.validate_object()?;
+                self.expect(TokenType::LeftBrace, "'{'")?;
+                let entries = self.parse_object()?;
+                self.expect(TokenType::RightBrace, "'}'")?;
+                Value::Object(entries)
             }
             TokenType::LeftBracket => {
-                self.advance();
-                self.validate_array()?;
+                self.expect(TokenType::LeftBracket, "'['")?;
+                let values = self.parse_array()?;
+                self.expect(TokenType::RightBracket, "']'")?;
+                Value::Array(values)
             }
             _ => {
-                return Err(Parser::token_error(token.position, token.value.as_ref()));
+                let token = self.lexer.next_token()?;
+                return Err(Parser::token_error("a value", &token));
             }
-        }
+        };
 
-        return Ok(());
+        Ok(value)
     }
 
-    fn validate_object(&mut self) -> Result<(), String> {
+    fn parse_object(&mut self) -> Result<Vec<(String, Value)>, ParseError> {
+        let mut entries = Vec::new();
+
         loop {
-            match self.get_current_token().token_type {
-                TokenType::RightBrace => break,
-                TokenType::String => {
-                    self.advance();
-                    if !self.is_colon() {
-                        let token = self.get_current_token();
-                        return Err(Parser::token_error(token.position, token.value.as_ref()));
-                    }
-                    self.advance();
-                    self.validate_value()?;
-                    self.advance();
-
-                    if self.is_comma() {
-                        self.advance();
-                        let token = self.get_current_token();
-                        if token.token_type != TokenType::String {
-                            return Err(Parser::token_error(token.position, token.value.as_ref()));
-                        }
-                        continue;
-                    } else if self.is_right_brace() {
-                        break;
-                    } else {
-                        let token = self.get_current_token();
-                        return Err(Parser::token_error(token.position, token.value.as_ref()));
+            if self.peek()?.token_type == TokenType::RightBrace {
+                break;
+            }
+
+            let key_token = self.expect(TokenType::String, "a string key or '}'")?;
+            let key = Parser::get_value(&key_token)?.to_string();
+
+            self.expect(TokenType::Colon, "':'")?;
+            let value = self.parse_value()?;
+            entries.push((key, value));
+
+            match self.peek()?.token_type {
+                TokenType::Comma => {
+                    self.expect(TokenType::Comma, "','")?;
+                    if self.peek()?.token_type != TokenType::String {
+                        let token = self.lexer.next_token()?;
+                        return Err(Parser::token_error("a string key", &token));
                     }
+                    continue;
                 }
+                TokenType::RightBrace => break,
                 _ => {
-                    let token = self.get_current_token();
-                    return Err(Parser::token_error(token.position, token.value.as_ref()));
+                    let token = self.lexer.next_token()?;
+                    return Err(Parser::token_error("',' or '}'", &token));
                 }
             }
         }
 
-        Ok(())
+        Ok(entries)
     }
 
-    fn validate_array(&mut self) -> Result<(), String> {
-        loop {
-            let token_type = self.get_current_token().token_type;
+    fn parse_array(&mut self) -> Result<Vec<Value>, ParseError> {
+        let mut values = Vec::new();
 
-            if token_type == TokenType::RightBracket {
+        loop {
+            if self.peek()?.token_type == TokenType::RightBracket {
                 break;
             }
-            self.validate_value()?;
-            self.advance();
-
-            if self.is_comma() {
-                self.advance();
-                let token = self.get_current_token();
-                if self.is_not_valid_array_next_value(&token.token_type) {
-                    return Err(Parser::token_error(token.position, token.value.as_ref()));
+            values.push(self.parse_value()?);
+
+            match self.peek()?.token_type {
+                TokenType::Comma => {
+                    self.expect(TokenType::Comma, "','")?;
+                    let next_type = self.peek()?.token_type;
+                    if self.is_not_valid_array_next_value(&next_type) {
+                        let token = self.lexer.next_token()?;
+                        return Err(Parser::token_error("a value", &token));
+                    }
+                    continue;
+                }
+                TokenType::RightBracket => break,
+                _ => {
+                    let token = self.lexer.next_token()?;
+                    return Err(Parser::token_error("',' or ']'", &token));
                 }
-                continue;
-            } else if self.is_right_bracket() {
-                break;
-            } else {
-                let token = self.get_current_token();
-                return Err(Parser::token_error(token.position, token.value.as_ref()));
             }
         }
 
-        Ok(())
+        Ok(values)
     }
 
-    fn validate_first_token(&self) -> Result<(), String> {
-        let token = self.get_current_token();
-
-        if token.token_type == TokenType::EndOfFile {
-            let msg = format!("empty JSON file");
-            return Err(msg);
-        } else if token.token_type != TokenType::LeftBrace
-            && token.token_type != TokenType::LeftBracket
-        {
-            match &token.value {
-                Some(value) => {
-                    if token.token_type == TokenType::Null && !Parser::is_null(value) {
-                        return Err(Parser::token_error(token.position, Some(value)));
-                    }
-                    if token.token_type == TokenType::Boolean && !Parser::is_boolean(value) {
-                        return Err(Parser::token_error(token.position, Some(value)));
-                    }
-                    if token.token_type == TokenType::Number && !Parser::is_number(value) {
-                        return Err(Parser::token_error(token.position, Some(value)));
-                    }
+    pub fn parse(&mut self) -> Result<Value, ParseError> {
+        let value = self.parse_value()?;
 
-                    if !self.expect_end_of_file() {
-                        return Err(Parser::token_error(token.position, Some(value)));
-                    }
-                }
-                None => {
-                    return Err(Parser::token_error(token.position, None));
-                }
-            }
+        if self.peek()?.token_type != TokenType::EndOfFile {
+            let token = self.lexer.next_token()?;
+            return Err(Parser::token_error("end of input", &token));
         }
 
-        return Ok(());
+        Ok(value)
     }
+}
 
-    pub fn parse(&mut self) -> Result<i32, String> {
-        self.validate_first_token()?;
-        let token_type = self.get_current_token().token_type;
+/// Parses JSON from a reader, so the call site need not read it into a
+/// `String` and hand it to [`Lexer::new`] itself. The reader's whole input is
+/// read into memory before parsing starts; it is not parsed incrementally.
+pub fn parse_reader<R: Read>(mut reader: R) -> Result<Value, ParseError> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .map_err(|err| ParseError::Io(err.to_string()))?;
 
-        if token_type != TokenType::LeftBrace && token_type != TokenType::LeftBracket {
-            return Ok(0);
-        }
+    Parser::new(Lexer::new(content)).parse()
+}
 
-        self.advance();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        match token_type {
-            TokenType::LeftBrace => {
-                self.validate_object()?;
-            }
-            TokenType::LeftBracket => {}
-            _ => {
-                let token = self.get_current_token();
-                return Err(Parser::token_error(token.position, token.value.as_ref()));
-            }
-        }
+    fn parse(source: &str) -> Value {
+        Parser::new(Lexer::new(source.to_string()))
+            .parse()
+            .unwrap_or_else(|err| panic!("failed to parse {source:?}: {err}"))
+    }
 
-        if !self.expect_end_of_file() {
-            let token = self.get_current_token();
-            return Err(Parser::token_error(token.position, token.value.as_ref()));
-        }
+    #[test]
+    fn parses_object() {
+        let value = parse(r#"{"a": true, "b": 1}"#);
+        assert_eq!(
+            value,
+            Value::Object(vec![
+                ("a".to_string(), Value::Bool(true)),
+                ("b".to_string(), Value::Int(1)),
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_array() {
+        let value = parse("[1, 2, 3]");
+        assert_eq!(
+            value,
+            Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
+        );
+    }
 
-        Ok(0)
+    #[test]
+    fn bare_literals_adjacent_to_array_closer() {
+        assert_eq!(parse("[true]"), Value::Array(vec![Value::Bool(true)]));
+        assert_eq!(parse("[false]"), Value::Array(vec![Value::Bool(false)]));
+        assert_eq!(parse("[null]"), Value::Array(vec![Value::Null]));
+        assert_eq!(
+            parse("[true,false,null]"),
+            Value::Array(vec![Value::Bool(true), Value::Bool(false), Value::Null])
+        );
+    }
+
+    #[test]
+    fn bare_literal_adjacent_to_object_closer() {
+        let value = parse(r#"{"a":true}"#);
+        assert_eq!(
+            value,
+            Value::Object(vec![("a".to_string(), Value::Bool(true))])
+        );
+    }
+
+    #[test]
+    fn distinguishes_int_and_float() {
+        let value = parse("[1, 1.5, 1e3, -0.5]");
+        assert_eq!(
+            value,
+            Value::Array(vec![
+                Value::Int(1),
+                Value::Float(1.5),
+                Value::Float(1000.0),
+                Value::Float(-0.5),
+            ])
+        );
     }
 }
@@ -1,35 +1,31 @@
 use std::{env, fs::File, io::Read, process};
 
-use json_parser::{Lexer, Parser};
+use json_parser::{render_diagnostic, Lexer, Parser};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     let filename = args.get(1);
-    let file;
-
-    match filename {
-        Some(name) => file = name,
+    let file = match filename {
+        Some(name) => name,
         None => panic!("file path not provided"),
-    }
+    };
 
     let mut content = String::new();
     let mut file = File::open(file).expect("could not open file");
     file.read_to_string(&mut content)
         .expect("could not read file");
 
-    let mut lexer = Lexer::new(content);
-    lexer.tokenize();
-
-    println!("{:#?}", lexer.get_tokens());
-
-    let mut parser = Parser::new(lexer.get_tokens());
+    let source = content.clone();
+    let lexer = Lexer::new(content);
+    let mut parser = Parser::new(lexer);
     match parser.parse() {
-        Ok(code) => {
+        Ok(value) => {
             println!("successfully parsed JSON file");
-            process::exit(code);
+            println!("{:#?}", value);
+            process::exit(0);
         }
-        Err(msg) => {
-            println!("{msg}");
+        Err(err) => {
+            println!("{}", render_diagnostic(&source, &err));
             process::exit(2);
         }
     }